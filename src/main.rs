@@ -6,17 +6,149 @@ use ggez::graphics;
 use ggez::input;
 use ggez::mint;
 use ggez::timer;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::Path;
 use tinyfiledialogs;
 
+/// The relative (dx, dy) offsets of the 8 neighbors of a cell.
+const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// The amount by which a single `+`/`-` press nudges `GameState::density`.
+const DENSITY_STEP: f64 = 0.05;
+
+/// The bounds `update_tick` may be multiplied/divided into via the
+/// speed-control keys.
+const MIN_UPDATE_TICK: std::time::Duration = std::time::Duration::from_millis(1);
+const MAX_UPDATE_TICK: std::time::Duration = std::time::Duration::from_millis(1000);
+/// The tick used while the turbo key is held, regardless of `update_tick`.
+const TURBO_UPDATE_TICK: std::time::Duration = std::time::Duration::from_millis(1);
+
+/// Controls how `World::step` treats cells at (and beyond) the edges
+/// of `GridParams::size`.
+#[derive(Clone, Copy, PartialEq)]
+enum SimMode {
+    /// Neighbor lookups wrap around the edges of the grid, so patterns
+    /// that cross a border reappear on the opposite side.
+    Toroidal,
+    /// The grid has no edges at all; live cells may wander arbitrarily
+    /// far from the window that's currently on screen.
+    Unbounded,
+}
+
+/// A sparse representation of the Game of Life board: just the
+/// coordinates of the currently-living cells.
+///
+/// This lets the simulation step only over live cells (and their
+/// neighbors) instead of scanning every cell in `GridParams::size`,
+/// and lets patterns exist (and be stepped correctly) outside of the
+/// window that's currently visible.
+#[derive(Clone, Serialize, Deserialize)]
+struct World {
+    live_cells: BTreeSet<(i64, i64)>,
+    /// How many consecutive generations each live cell has been alive.
+    /// A cell absent here but present in `live_cells` is age 1 (just born).
+    ///
+    /// Skipped on (de)serialization: `serde_json` can't serialize a map
+    /// keyed by tuples, and age is cosmetic, so a loaded world simply
+    /// starts every live cell at age 1.
+    #[serde(skip)]
+    ages: BTreeMap<(i64, i64), u32>,
+}
+
+impl World {
+    fn new() -> World {
+        World {
+            live_cells: BTreeSet::new(),
+            ages: BTreeMap::new(),
+        }
+    }
+
+    fn is_alive(&self, pos: (i64, i64)) -> bool {
+        self.live_cells.contains(&pos)
+    }
+
+    fn age(&self, pos: (i64, i64)) -> u32 {
+        self.ages.get(&pos).copied().unwrap_or(1)
+    }
+
+    fn set_alive(&mut self, pos: (i64, i64), alive: bool) {
+        if alive {
+            if self.live_cells.insert(pos) {
+                self.ages.insert(pos, 1);
+            }
+        } else {
+            self.live_cells.remove(&pos);
+            self.ages.remove(&pos);
+        }
+    }
+
+    /// Advances the world by a single generation according to B3/S23,
+    /// returning the **new** world.
+    ///
+    /// Only live cells and their neighbors are ever considered, so the
+    /// cost is proportional to the number of live cells rather than to
+    /// `params.size`.
+    fn step(&self, params: &GridParams, mode: SimMode) -> World {
+        let mut neighbor_counts: BTreeMap<(i64, i64), u8> = BTreeMap::new();
+        for &(x, y) in &self.live_cells {
+            for (dx, dy) in NEIGHBOR_OFFSETS.iter() {
+                let neighbor = match mode {
+                    SimMode::Toroidal => (
+                        (x + dx).rem_euclid(params.size.0 as i64),
+                        (y + dy).rem_euclid(params.size.1 as i64),
+                    ),
+                    SimMode::Unbounded => (x + dx, y + dy),
+                };
+                *neighbor_counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+
+        let mut live_cells = BTreeSet::new();
+        let mut ages = BTreeMap::new();
+        for (&pos, &count) in &neighbor_counts {
+            if count == 3 || (count == 2 && self.is_alive(pos)) {
+                live_cells.insert(pos);
+                let age = if self.is_alive(pos) { self.age(pos) + 1 } else { 1 };
+                ages.insert(pos, age);
+            }
+        }
+        World { live_cells, ages }
+    }
+}
+
+/// A small gradient from newborn (bright) to long-lived (muted),
+/// indexed by a cell's age and clamped at the last entry.
+fn age_color(age: u32) -> graphics::Color {
+    const PALETTE: [(f32, f32, f32); 5] = [
+        (1.0, 1.0, 1.0),
+        (1.0, 0.9, 0.3),
+        (0.9, 0.6, 0.2),
+        (0.7, 0.3, 0.2),
+        (0.4, 0.4, 0.6),
+    ];
+    let (r, g, b) = PALETTE[(age as usize - 1).min(PALETTE.len() - 1)];
+    graphics::Color::new(r, g, b, 1.0)
+}
+
 struct GridParams {
-    /// The number of cells in each (row, column) of the grid.
+    /// The number of cells in each (row, column) of the logical grid,
+    /// which the camera pans and zooms over independently of the window.
     size: (usize, usize),
     /// The size of each cell (width, height) in pixels.
     cell_size: (usize, usize),
-    /// The color with which to fill a cell (if needed).
-    cell_color: graphics::Color,
     /// The width of the lines that mark the grid in pixels.
     line_width: f32,
     /// The color with which to draw the lines that mark the grid.
@@ -70,42 +202,47 @@ fn generate_grid_mesh(
     builder.build(ctx)
 }
 
-/// Generates a `Mesh` for the grid cells according
-/// to the given `GridParams` and grid state.
+/// Generates a `Mesh` for the grid cells according to the given
+/// `GridParams` and `World`.
 ///
-/// `grid_state` **has** to be *horizontally packed*,
-/// meaning that the outer `Vec` holds many rows.
-///
-/// The dimensions of `grid_state` are taken from
-/// `params`, and are **not** checked. It's up to
-/// the caller to make sure they are synchronized (`grid_state` may be bigger).  
+/// Only live cells that fall inside the camera's current viewport are
+/// drawn; in `SimMode::Unbounded` a pattern may well have live cells
+/// far outside of `params.size`, and those are reachable by panning
+/// rather than being permanently skipped.
 fn generate_grid_cells_mesh(
     ctx: &mut ggez::Context,
     params: &GridParams,
-    grid_state: &Vec<Vec<bool>>,
+    world: &World,
+    camera: &Camera,
 ) -> ggez::GameResult<graphics::Mesh> {
     let mut builder = graphics::MeshBuilder::new();
     let mut num_rectangles = 0;
 
-    for row in 0..params.size.1 {
-        let y = (row * params.cell_size.1) as f32 + params.line_width * 0.5;
-        for column in 0..params.size.0 {
-            let x = (column * params.cell_size.0) as f32 + params.line_width * 0.5;
-
-            if grid_state[row][column] {
-                builder.rectangle(
-                    graphics::DrawMode::fill(),
-                    graphics::Rect::new(
-                        x,
-                        y,
-                        params.cell_size.0 as f32 - params.line_width,
-                        params.cell_size.1 as f32 - params.line_width,
-                    ),
-                    params.cell_color,
-                );
-                num_rectangles += 1;
-            }
+    let (window_width, window_height) = graphics::drawable_size(ctx);
+    let min_x = (camera.offset.x / params.cell_size.0 as f32).floor() as i64;
+    let min_y = (camera.offset.y / params.cell_size.1 as f32).floor() as i64;
+    let max_x = ((camera.offset.x + window_width / camera.zoom) / params.cell_size.0 as f32).ceil() as i64;
+    let max_y = ((camera.offset.y + window_height / camera.zoom) / params.cell_size.1 as f32).ceil() as i64;
+
+    for &(x, y) in &world.live_cells {
+        if x < min_x || x > max_x || y < min_y || y > max_y {
+            continue;
         }
+
+        let draw_x = (x * params.cell_size.0 as i64) as f32 + params.line_width * 0.5;
+        let draw_y = (y * params.cell_size.1 as i64) as f32 + params.line_width * 0.5;
+
+        builder.rectangle(
+            graphics::DrawMode::fill(),
+            graphics::Rect::new(
+                draw_x,
+                draw_y,
+                params.cell_size.0 as f32 - params.line_width,
+                params.cell_size.1 as f32 - params.line_width,
+            ),
+            age_color(world.age((x, y))),
+        );
+        num_rectangles += 1;
     }
 
     // Only build the mesh if it isn't empty, as it
@@ -120,74 +257,200 @@ fn generate_grid_cells_mesh(
     }
 }
 
-/// Calculates the indice of the grid cell under the given point.Result
-///
-/// If point is **not** on a grid cell, return error.
-fn calculate_grid_cell_indices(
-    params: &GridParams,
-    point: mint::Point2<f32>,
-) -> Result<mint::Point2<usize>, ()> {
-    let x: usize = (point.x / params.cell_size.0 as f32) as usize;
-    let y: usize = (point.y / params.cell_size.1 as f32) as usize;
+/// Maps a point in grid-pixel space to a grid-cell coordinate,
+/// clamping it to the bounds of the grid instead of failing when it
+/// falls outside (e.g. a drag whose cursor has momentarily left the
+/// window, or a camera pan that's moved the grid off-screen).
+fn clamp_point_to_grid_cell(params: &GridParams, point: mint::Point2<f32>) -> (i64, i64) {
+    let x = (point.x / params.cell_size.0 as f32).floor() as i64;
+    let y = (point.y / params.cell_size.1 as f32).floor() as i64;
+
+    let x = x.max(0).min(params.size.0 as i64 - 1);
+    let y = y.max(0).min(params.size.1 as i64 - 1);
+    (x, y)
+}
 
-    if x < params.size.0 && y < params.size.1 {
-        Ok(mint::Point2 { x: x, y: y })
-    } else {
-        Err(())
+/// Walks a Bresenham line between `start` and `end` (inclusive of both
+/// endpoints) over integer grid-cell coordinates, stepping along the
+/// major axis and accumulating error on the minor one.
+fn bresenham_line(start: (i64, i64), end: (i64, i64)) -> Vec<(i64, i64)> {
+    let (x0, y0) = start;
+    let (x1, y1) = end;
+
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+
+    let mut points = Vec::new();
+    let mut x = x0;
+    let mut y = y0;
+    let mut err = dx + dy;
+    loop {
+        points.push((x, y));
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
     }
+    points
 }
 
-/// Updates the grid state according to the rules of Game of Life.
-///
-/// Returns a **new** grid_state.
-fn update_grid_state(params: &GridParams, grid_state: &Vec<Vec<bool>>) -> Vec<Vec<bool>> {
-    let mut new_state = grid_state.clone();
-    // Update each cell in the grid.
-    for row in 0..params.size.1 {
-        for column in 0..params.size.0 {
-            const INDICE_OFFSETS: [[isize; 2]; 8] = [
-                [-1, -1],
-                [0, -1],
-                [1, -1],
-                [-1, 0],
-                [1, 0],
-                [-1, 1],
-                [0, 1],
-                [1, 1],
-            ];
-
-            // Check neighbors.
-            let mut living_neighbors: u32 = 0;
-            for indices in &INDICE_OFFSETS {
-                let x = column as isize + indices[0];
-                let y = row as isize + indices[1];
-
-                if (0..(params.size.0 as isize)).contains(&x)
-                    && (0..(params.size.1 as isize)).contains(&y)
-                {
-                    living_neighbors += if grid_state[y as usize][x as usize] {
-                        1
-                    } else {
-                        0
-                    };
+/// Returns the `(width, height)` of the box that runs from the origin
+/// `(0, 0)` to the furthest live cell of `world` along each axis, or
+/// `(0, 0)` when there are no live cells. Live cells at negative
+/// coordinates lie outside that box; an axis on which every live cell
+/// is negative gets a size of 0.
+fn world_bounding_box(world: &World) -> (usize, usize) {
+    let max_x = world.live_cells.iter().map(|&(x, _)| x).max();
+    let max_y = world.live_cells.iter().map(|&(_, y)| y).max();
+    match (max_x, max_y) {
+        (Some(max_x), Some(max_y)) => ((max_x + 1).max(0) as usize, (max_y + 1).max(0) as usize),
+        _ => (0, 0),
+    }
+}
+
+/// Reads a plaintext `.cells` pattern (as used by e.g. the
+/// LifeWiki pattern archive): `!`-prefixed lines are comments, and
+/// every other character is dead (`.`, `0`, space) or alive (anything
+/// else). Returns the parsed `World` along with its `(width, height)`.
+fn world_from_plaintext(contents: &str) -> (World, (usize, usize)) {
+    let mut world = World::new();
+    let mut width = 0;
+    let mut height = 0i64;
+    for line in contents.lines() {
+        if line.starts_with('!') {
+            continue;
+        }
+        for (x, cell) in line.chars().enumerate() {
+            if cell != '.' && cell != '0' && cell != ' ' {
+                world.set_alive((x as i64, height), true);
+            }
+        }
+        width = width.max(line.chars().count());
+        height += 1;
+    }
+    (world, (width, height as usize))
+}
+
+/// Writes `world` as a plaintext `.cells` pattern covering `size`.
+fn world_to_plaintext(world: &World, size: (usize, usize)) -> String {
+    let mut contents = String::new();
+    for y in 0..size.1 {
+        for x in 0..size.0 {
+            contents.push(if world.is_alive((x as i64, y as i64)) {
+                'O'
+            } else {
+                '.'
+            });
+        }
+        contents.push('\n');
+    }
+    contents
+}
+
+/// Reads a pattern in the RLE format: a `x = m, y = n` header followed
+/// by a run-length encoded body where a decimal count prefixes a tag
+/// (`b` = dead, `o` = alive, `$` = end of row, `!` = end of pattern).
+/// Returns the parsed `World` along with the header's `(width,
+/// height)`.
+fn world_from_rle(contents: &str) -> (World, (usize, usize)) {
+    let mut width = 0;
+    let mut height = 0;
+    let mut body = String::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        if line.starts_with('x') {
+            for field in line.split(',') {
+                let mut parts = field.splitn(2, '=');
+                let key = parts.next().unwrap_or("").trim();
+                let value = parts.next().unwrap_or("").trim();
+                match key {
+                    "x" => width = value.parse().unwrap_or(0),
+                    "y" => height = value.parse().unwrap_or(0),
+                    _ => (),
                 }
             }
+            continue;
+        }
+        body.push_str(line);
+    }
 
-            let is_living = grid_state[row][column];
-            if is_living {
-                // Any live cell with fewer than two live neighbours dies, as if by underpopulation.
-                // Any live cell with more than three live neighbours dies, as if by overpopulation.
-                if living_neighbors < 2 || living_neighbors > 3 {
-                    new_state[row][column] = false;
+    let mut world = World::new();
+    let mut x = 0i64;
+    let mut y = 0i64;
+    let mut count = String::new();
+    for tag in body.chars() {
+        match tag {
+            '0'..='9' => count.push(tag),
+            'b' | 'o' | '$' => {
+                let run: i64 = count.parse().unwrap_or(1);
+                count.clear();
+                match tag {
+                    'b' => x += run,
+                    'o' => {
+                        for _ in 0..run {
+                            world.set_alive((x, y), true);
+                            x += 1;
+                        }
+                    }
+                    '$' => {
+                        y += run;
+                        x = 0;
+                    }
+                    _ => unreachable!(),
                 }
             }
-            // Any dead cell with exactly three live neighbours becomes a live cell, as if by reproduction.
-            else if living_neighbors == 3 {
-                new_state[row][column] = true;
+            '!' => break,
+            _ => (),
+        }
+    }
+
+    (world, (width, height))
+}
+
+/// Writes `world` as an RLE pattern covering `size`.
+fn world_to_rle(world: &World, size: (usize, usize)) -> String {
+    let mut contents = format!("x = {}, y = {}\n", size.0, size.1);
+    for y in 0..size.1 {
+        let row_width = (0..size.0)
+            .rev()
+            .find(|&x| world.is_alive((x as i64, y as i64)))
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
+        let mut x = 0;
+        while x < row_width {
+            let alive = world.is_alive((x as i64, y as i64));
+            let mut run = 1;
+            while x + run < row_width && world.is_alive(((x + run) as i64, y as i64)) == alive {
+                run += 1;
+            }
+            if run > 1 {
+                contents.push_str(&run.to_string());
             }
+            contents.push(if alive { 'o' } else { 'b' });
+            x += run;
         }
+        contents.push('$');
     }
-    new_state
+    if contents.ends_with('$') {
+        contents.pop();
+    }
+    contents.push('!');
+    contents
 }
 
 /// Updates the size of the window according to the given grid parameters.ggez
@@ -203,17 +466,75 @@ fn update_window_size(ctx: &mut ggez::Context, params: &GridParams) -> ggez::Gam
     )
 }
 
+/// A 2D camera used to pan and zoom the view of the grid independently
+/// of the window's own size, so a board bigger than the window can
+/// still be navigated.
+struct Camera {
+    /// The top-left corner of the view, in grid-pixel space.
+    offset: mint::Point2<f32>,
+    zoom: f32,
+}
+
+impl Camera {
+    fn new() -> Camera {
+        Camera {
+            offset: mint::Point2 { x: 0.0, y: 0.0 },
+            zoom: 1.0,
+        }
+    }
+
+    /// The `DrawParam` that applies this camera's pan and zoom to a mesh.
+    fn draw_param(&self) -> graphics::DrawParam {
+        graphics::DrawParam::new()
+            .dest(mint::Point2 {
+                x: -self.offset.x * self.zoom,
+                y: -self.offset.y * self.zoom,
+            })
+            .scale(mint::Vector2 {
+                x: self.zoom,
+                y: self.zoom,
+            })
+    }
+
+    /// Maps a point in screen space back to grid-pixel space, undoing
+    /// this camera's pan and zoom.
+    fn screen_to_grid(&self, point: mint::Point2<f32>) -> mint::Point2<f32> {
+        mint::Point2 {
+            x: point.x / self.zoom + self.offset.x,
+            y: point.y / self.zoom + self.offset.y,
+        }
+    }
+}
+
 struct GameState {
     grid_params: GridParams,
     grid_mesh: graphics::Mesh,
-    grid_state: Vec<Vec<bool>>,
+    grid_state: World,
+    /// The cached mesh for `grid_state`'s live cells, rebuilt only when
+    /// `cells_dirty` is set.
+    cells_mesh: Option<graphics::Mesh>,
+    cells_dirty: bool,
+    camera: Camera,
+    sim_mode: SimMode,
     playing: bool,
     last_update: std::time::Duration,
     update_tick: std::time::Duration,
-    mouse_button_pressed_last_frame: bool,
+    /// The number of generations simulated so far.
+    generation: u64,
+    draw_last_cell: Option<(i64, i64)>,
+    erase_last_cell: Option<(i64, i64)>,
+    /// The fraction of cells set alive by a random soup (`R`).
+    density: f64,
     save_key_pressed_last_frame: bool,
     load_key_pressed_last_frame: bool,
     play_key_pressed_last_frame: bool,
+    mode_key_pressed_last_frame: bool,
+    random_key_pressed_last_frame: bool,
+    increase_density_key_pressed_last_frame: bool,
+    decrease_density_key_pressed_last_frame: bool,
+    frame_step_key_pressed_last_frame: bool,
+    increase_speed_key_pressed_last_frame: bool,
+    decrease_speed_key_pressed_last_frame: bool,
 }
 
 impl GameState {
@@ -221,25 +542,37 @@ impl GameState {
         let params = GridParams {
             size: (20, 15),
             cell_size: (20, 20),
-            cell_color: graphics::WHITE,
             line_width: 2.0,
             line_color: graphics::BLACK,
         };
         update_window_size(ctx, &params)?;
 
         let mesh = generate_grid_mesh(ctx, &params)?;
-        let default_grid = vec![vec![false; params.size.0]; params.size.1];
         let state = GameState {
             grid_params: params,
             grid_mesh: mesh,
-            grid_state: default_grid,
+            grid_state: World::new(),
+            cells_mesh: None,
+            cells_dirty: true,
+            camera: Camera::new(),
+            sim_mode: SimMode::Toroidal,
             playing: false,
             last_update: std::time::Duration::default(),
             update_tick: std::time::Duration::from_millis(10),
-            mouse_button_pressed_last_frame: false,
+            generation: 0,
+            draw_last_cell: None,
+            erase_last_cell: None,
+            density: 0.3,
             save_key_pressed_last_frame: false,
             load_key_pressed_last_frame: false,
             play_key_pressed_last_frame: false,
+            mode_key_pressed_last_frame: false,
+            random_key_pressed_last_frame: false,
+            increase_density_key_pressed_last_frame: false,
+            decrease_density_key_pressed_last_frame: false,
+            frame_step_key_pressed_last_frame: false,
+            increase_speed_key_pressed_last_frame: false,
+            decrease_speed_key_pressed_last_frame: false,
         };
         Ok(state)
     }
@@ -249,28 +582,81 @@ impl event::EventHandler for GameState {
     fn update(&mut self, ctx: &mut ggez::Context) -> ggez::GameResult {
         let time = ggez::timer::time_since_start(ctx);
         if !self.playing {
-            let pressed = input::mouse::button_pressed(ctx, input::mouse::MouseButton::Left);
-            if self.mouse_button_pressed_last_frame && !pressed {
-                let position = input::mouse::position(ctx);
-                match calculate_grid_cell_indices(&self.grid_params, position) {
-                    Err(()) => (),
-                    Ok(point) => {
-                        let value = self.grid_state[point.y][point.x];
-                        self.grid_state[point.y][point.x] = !value;
-                    }
+            let left_pressed = input::mouse::button_pressed(ctx, input::mouse::MouseButton::Left);
+            if left_pressed {
+                let position = self.camera.screen_to_grid(input::mouse::position(ctx));
+                let cell = clamp_point_to_grid_cell(&self.grid_params, position);
+                let stroke = match self.draw_last_cell {
+                    Some(last) => bresenham_line(last, cell),
+                    None => vec![cell],
+                };
+                for point in stroke {
+                    self.grid_state.set_alive(point, true);
                 }
+                self.draw_last_cell = Some(cell);
+                self.cells_dirty = true;
+            } else {
+                self.draw_last_cell = None;
+            }
+
+            let right_pressed = input::mouse::button_pressed(ctx, input::mouse::MouseButton::Right);
+            if right_pressed {
+                let position = self.camera.screen_to_grid(input::mouse::position(ctx));
+                let cell = clamp_point_to_grid_cell(&self.grid_params, position);
+                let stroke = match self.erase_last_cell {
+                    Some(last) => bresenham_line(last, cell),
+                    None => vec![cell],
+                };
+                for point in stroke {
+                    self.grid_state.set_alive(point, false);
+                }
+                self.erase_last_cell = Some(cell);
+                self.cells_dirty = true;
+            } else {
+                self.erase_last_cell = None;
+            }
+
+            let up = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Up);
+            let down = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Down);
+            let left = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Left);
+            let right = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Right);
+            let pan_step = 10.0 / self.camera.zoom;
+            if up || down || left || right {
+                if up {
+                    self.camera.offset.y -= pan_step;
+                }
+                if down {
+                    self.camera.offset.y += pan_step;
+                }
+                if left {
+                    self.camera.offset.x -= pan_step;
+                }
+                if right {
+                    self.camera.offset.x += pan_step;
+                }
+                self.cells_dirty = true;
             }
-            self.mouse_button_pressed_last_frame = pressed;
 
             let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::S);
             if !self.save_key_pressed_last_frame && pressed {
                 match tinyfiledialogs::save_file_dialog("Save", "./grid-state.json") {
                     None => (),
                     Some(file) => {
-                        let serialized = serde_json::to_string(&self.grid_state).unwrap();
-                        match File::create(file) {
-                            Ok(mut file) => file.write_all(serialized.as_bytes()).unwrap(),
-                            _ => (),
+                        let extension = Path::new(&file)
+                            .extension()
+                            .and_then(|ext| ext.to_str())
+                            .unwrap_or("json");
+                        let size = world_bounding_box(&self.grid_state);
+                        let serialized = match extension {
+                            "cells" => Some(world_to_plaintext(&self.grid_state, size)),
+                            "rle" => Some(world_to_rle(&self.grid_state, size)),
+                            _ => serde_json::to_string(&self.grid_state).ok(),
+                        };
+                        if let Some(serialized) = serialized {
+                            match File::create(file) {
+                                Ok(mut file) => file.write_all(serialized.as_bytes()).unwrap(),
+                                _ => (),
+                            }
                         }
                     }
                 }
@@ -281,24 +667,41 @@ impl event::EventHandler for GameState {
             if !self.load_key_pressed_last_frame && pressed {
                 match tinyfiledialogs::open_file_dialog("Open", "./", None) {
                     None => (),
-                    Some(file) => match File::open(file) {
-                        Ok(mut file) => {
+                    Some(file) => match File::open(&file) {
+                        Ok(mut handle) => {
                             let mut file_contents = String::new();
-                            file.read_to_string(&mut file_contents).unwrap();
-
-                            match serde_json::from_str(&file_contents) {
-                                Ok(deserialized) => {
-                                    self.grid_state = deserialized;
-                                    let new_size =
-                                        (self.grid_state[0].len(), self.grid_state.len());
-                                    if self.grid_params.size != new_size {
-                                        self.grid_params.size = new_size;
-                                        self.grid_mesh =
-                                            generate_grid_mesh(ctx, &self.grid_params)?;
-                                        update_window_size(ctx, &self.grid_params)?;
+                            handle.read_to_string(&mut file_contents).unwrap();
+
+                            let extension = Path::new(&file)
+                                .extension()
+                                .and_then(|ext| ext.to_str())
+                                .unwrap_or("json");
+                            let loaded = match extension {
+                                "cells" => Some(world_from_plaintext(&file_contents)),
+                                "rle" => Some(world_from_rle(&file_contents)),
+                                _ => match serde_json::from_str::<World>(&file_contents) {
+                                    Ok(world) => {
+                                        let size = world_bounding_box(&world);
+                                        Some((world, size))
                                     }
+                                    _ => None,
+                                },
+                            };
+
+                            if let Some((world, new_size)) = loaded {
+                                self.grid_state = world;
+                                self.cells_dirty = true;
+                                if new_size.0 > 0
+                                    && new_size.1 > 0
+                                    && self.grid_params.size != new_size
+                                {
+                                    // Only the logical grid (used for toroidal
+                                    // wrapping and the line mesh) changes size;
+                                    // the window stays put and the camera is
+                                    // used to pan/zoom to the loaded pattern.
+                                    self.grid_params.size = new_size;
+                                    self.grid_mesh = generate_grid_mesh(ctx, &self.grid_params)?;
                                 }
-                                _ => (),
                             }
                         }
                         _ => (),
@@ -306,9 +709,57 @@ impl event::EventHandler for GameState {
                 }
             }
             self.load_key_pressed_last_frame = pressed;
-        } else if (time - self.last_update) >= self.update_tick {
-            self.grid_state = update_grid_state(&self.grid_params, &self.grid_state);
-            self.last_update = time;
+
+            let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::M);
+            if !self.mode_key_pressed_last_frame && pressed {
+                self.sim_mode = match self.sim_mode {
+                    SimMode::Toroidal => SimMode::Unbounded,
+                    SimMode::Unbounded => SimMode::Toroidal,
+                };
+            }
+            self.mode_key_pressed_last_frame = pressed;
+
+            let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::R);
+            if !self.random_key_pressed_last_frame && pressed {
+                let mut rng = rand::thread_rng();
+                for y in 0..self.grid_params.size.1 as i64 {
+                    for x in 0..self.grid_params.size.0 as i64 {
+                        self.grid_state.set_alive((x, y), rng.gen_bool(self.density));
+                    }
+                }
+                self.cells_dirty = true;
+            }
+            self.random_key_pressed_last_frame = pressed;
+
+            let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Equals);
+            if !self.increase_density_key_pressed_last_frame && pressed {
+                self.density = (self.density + DENSITY_STEP).min(1.0);
+            }
+            self.increase_density_key_pressed_last_frame = pressed;
+
+            let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Minus);
+            if !self.decrease_density_key_pressed_last_frame && pressed {
+                self.density = (self.density - DENSITY_STEP).max(0.0);
+            }
+            self.decrease_density_key_pressed_last_frame = pressed;
+
+            let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Period);
+            if !self.frame_step_key_pressed_last_frame && pressed {
+                self.grid_state = self.grid_state.step(&self.grid_params, self.sim_mode);
+                self.generation += 1;
+                self.cells_dirty = true;
+            }
+            self.frame_step_key_pressed_last_frame = pressed;
+        } else {
+            let turbo = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Tab);
+            let effective_tick = if turbo { TURBO_UPDATE_TICK } else { self.update_tick };
+
+            if (time - self.last_update) >= effective_tick {
+                self.grid_state = self.grid_state.step(&self.grid_params, self.sim_mode);
+                self.generation += 1;
+                self.cells_dirty = true;
+                self.last_update = time;
+            }
         }
 
         let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::Return);
@@ -318,23 +769,45 @@ impl event::EventHandler for GameState {
         }
         self.play_key_pressed_last_frame = pressed;
 
+        let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::RBracket);
+        if !self.increase_speed_key_pressed_last_frame && pressed {
+            self.update_tick = (self.update_tick / 2).max(MIN_UPDATE_TICK);
+        }
+        self.increase_speed_key_pressed_last_frame = pressed;
+
+        let pressed = input::keyboard::is_key_pressed(ctx, input::keyboard::KeyCode::LBracket);
+        if !self.decrease_speed_key_pressed_last_frame && pressed {
+            self.update_tick = (self.update_tick * 2).min(MAX_UPDATE_TICK);
+        }
+        self.decrease_speed_key_pressed_last_frame = pressed;
+
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut ggez::Context) -> ggez::GameResult {
         graphics::clear(ctx, [0.1, 0.2, 0.3, 1.0].into());
 
+        if self.cells_dirty {
+            self.cells_mesh =
+                generate_grid_cells_mesh(ctx, &self.grid_params, &self.grid_state, &self.camera).ok();
+            self.cells_dirty = false;
+        }
+
+        let camera_transform = self.camera.draw_param();
         // draw the grid outline
-        graphics::draw(ctx, &self.grid_mesh, (mint::Point2 { x: 0.0, y: 0.0 },))?;
-        // draw the grid cells
-        let grid_cells_mesh = generate_grid_cells_mesh(ctx, &self.grid_params, &self.grid_state);
-        match grid_cells_mesh {
-            Ok(mesh) => graphics::draw(ctx, &mesh, (mint::Point2 { x: 0.0, y: 0.0 },))?,
-            _ => (),
+        graphics::draw(ctx, &self.grid_mesh, camera_transform)?;
+        // draw the (cached) grid cells
+        if let Some(mesh) = &self.cells_mesh {
+            graphics::draw(ctx, mesh, camera_transform)?;
         }
 
         // Print the fps counter to the screen.
-        let fps_counter = graphics::Text::new(format!("{}", timer::fps(ctx) as i32));
+        let fps_counter = graphics::Text::new(format!(
+            "{} fps | gen {} | tick {} ms",
+            timer::fps(ctx) as i32,
+            self.generation,
+            self.update_tick.as_millis(),
+        ));
         graphics::draw(ctx, &fps_counter, (mint::Point2 { x: 0.0, y: 0.0 },))?;
 
         graphics::present(ctx)?;
@@ -345,6 +818,16 @@ impl event::EventHandler for GameState {
         graphics::set_screen_coordinates(ctx, graphics::Rect::new(0.0, 0.0, width, height))
             .unwrap();
     }
+
+    fn mouse_wheel_event(&mut self, _ctx: &mut ggez::Context, _x: f32, y: f32) {
+        const ZOOM_STEP: f32 = 0.1;
+        const MIN_ZOOM: f32 = 0.25;
+        const MAX_ZOOM: f32 = 4.0;
+        self.camera.zoom = (self.camera.zoom + y * ZOOM_STEP)
+            .max(MIN_ZOOM)
+            .min(MAX_ZOOM);
+        self.cells_dirty = true;
+    }
 }
 
 pub fn main() -> ggez::GameResult {
@@ -357,3 +840,70 @@ pub fn main() -> ggez::GameResult {
     let state = &mut GameState::new(ctx)?;
     event::run(ctx, event_loop, state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rle_expands_run_length_body() {
+        let (world, size) = world_from_rle("x = 3, y = 2\n3o$2bo!");
+        assert_eq!(size, (3, 2));
+        let expected: BTreeSet<(i64, i64)> = [(0, 0), (1, 0), (2, 0), (2, 1)].iter().cloned().collect();
+        assert_eq!(world.live_cells, expected);
+    }
+
+    #[test]
+    fn rle_round_trips_through_world_to_rle() {
+        let mut world = World::new();
+        for &pos in &[(0i64, 0i64), (1, 0), (2, 0), (2, 1)] {
+            world.set_alive(pos, true);
+        }
+        let size = world_bounding_box(&world);
+        let rle = world_to_rle(&world, size);
+        let (roundtripped, roundtripped_size) = world_from_rle(&rle);
+        assert_eq!(roundtripped_size, size);
+        assert_eq!(roundtripped.live_cells, world.live_cells);
+    }
+
+    #[test]
+    fn rle_ignores_comment_lines_and_stops_at_bang() {
+        let (world, size) = world_from_rle("#C a comment\nx = 1, y = 1\nbo!\nbo$bo!");
+        assert_eq!(size, (1, 1));
+        let expected: BTreeSet<(i64, i64)> = [(1, 0)].iter().cloned().collect();
+        assert_eq!(world.live_cells, expected, "parsing should stop at the first '!'");
+    }
+
+    #[test]
+    fn plaintext_reads_live_cells_and_ignores_comment_lines() {
+        let (world, size) = world_from_plaintext("!Name: test\n.O.\nO.O\n...\n");
+        assert_eq!(size, (3, 3));
+        let expected: BTreeSet<(i64, i64)> = [(1, 0), (0, 1), (2, 1)].iter().cloned().collect();
+        assert_eq!(world.live_cells, expected);
+    }
+
+    #[test]
+    fn plaintext_counts_trailing_blank_rows() {
+        let (_world, size) = world_from_plaintext(".O.\n\n");
+        assert_eq!(size, (3, 2));
+    }
+
+    #[test]
+    fn bresenham_line_of_a_single_point_is_just_that_point() {
+        assert_eq!(bresenham_line((2, 3), (2, 3)), vec![(2, 3)]);
+    }
+
+    #[test]
+    fn bresenham_line_walks_a_diagonal_inclusive_of_both_endpoints() {
+        let line = bresenham_line((0, 0), (3, 3));
+        assert_eq!(line, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn bresenham_line_walks_a_shallow_slope() {
+        let line = bresenham_line((0, 0), (4, 1));
+        assert_eq!(line.first(), Some(&(0, 0)));
+        assert_eq!(line.last(), Some(&(4, 1)));
+        assert_eq!(line.len(), 5);
+    }
+}